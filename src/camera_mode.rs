@@ -0,0 +1,65 @@
+use macroquad::prelude::*;
+use crate::consts::{CHASE_DISTANCE, CHASE_HEIGHT};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum CameraMode {
+    Free,
+    Chase,
+    Orbit,
+}
+
+impl CameraMode {
+    pub fn next(self) -> Self {
+        match self {
+            CameraMode::Free => CameraMode::Chase,
+            CameraMode::Chase => CameraMode::Orbit,
+            CameraMode::Orbit => CameraMode::Free,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CameraMode::Free => "First Person Camera",
+            CameraMode::Chase => "Chase Camera",
+            CameraMode::Orbit => "Orbit Camera",
+        }
+    }
+}
+
+pub fn camera_for_mode(
+    mode: CameraMode,
+    position: Vec3,
+    front: Vec3,
+    up: Vec3,
+    plane_position: Vec3,
+    chase_direction: Vec3,
+    orbit_distance: f32,
+) -> Camera3D {
+    match mode {
+        // WASD/mouse fly-cam, independent of the airplane
+        CameraMode::Free => Camera3D {
+            position,
+            up,
+            target: position + front,
+            ..Default::default()
+        },
+        // trail behind the airplane along its direction of travel
+        CameraMode::Chase => Camera3D {
+            position: plane_position - chase_direction * CHASE_DISTANCE + vec3(0.0, CHASE_HEIGHT, 0.0),
+            up: vec3(0.0, 1.0, 0.0),
+            target: plane_position,
+            ..Default::default()
+        },
+        // mouse look swings the camera around the airplane instead of turning it,
+        // looking up is held level so the camera never orbits under the ground
+        CameraMode::Orbit => {
+            let orbit_direction = vec3(front.x, front.y.min(0.0), front.z).normalize();
+            Camera3D {
+                position: plane_position - orbit_direction * orbit_distance,
+                up: vec3(0.0, 1.0, 0.0),
+                target: plane_position,
+                ..Default::default()
+            }
+        }
+    }
+}
@@ -2,8 +2,28 @@
 
 pub static  MOVE_SPEED: f32 = 0.1;
 pub static  LOOK_SPEED: f32 = 0.1;
+pub static  SLEW_SPEED: f32 = 0.2;
+
+pub static  CHASE_DISTANCE: f32 = 8.0;
+pub static  CHASE_HEIGHT: f32 = 2.0;
+pub static  ORBIT_DISTANCE: f32 = 10.0;
+pub static  ORBIT_MIN_DISTANCE: f32 = 2.0;
+pub static  ORBIT_MAX_DISTANCE: f32 = 200.0;
+pub static  ORBIT_ZOOM_STEP: f32 = 0.9;
 
 pub static PROFILER: bool = true;
 
+// frame rate caps cycled with <F>, None leaves it to vsync. Per-frame steps are
+// scaled by frame time against REFERENCE_HZ, so a cap only changes how often we draw
+pub static FPS_CAPS: [Option<u32>; 5] = [None, Some(30), Some(60), Some(120), Some(144)];
+
+// speeds and steps are tuned per frame at this rate
+pub static REFERENCE_HZ: f32 = 60.0;
+// longest frame we scale for, so a stall doesn't teleport the airplane
+pub static MAX_FRAME_TIME: f32 = 0.1;
+
 use macroquad::prelude::*;
-pub static  FSBLUE: Color = Color::new(0.10, 0.20, 0.30, 1.00);
\ No newline at end of file
+pub static  FSBLUE: Color = Color::new(0.10, 0.20, 0.30, 1.00);
+
+// where <V> puts the camera relative to the airplane
+pub static OBSERVER_SNAP_OFFSET: Vec3 = Vec3::new(-6.0, 2.0, 0.0);
\ No newline at end of file
@@ -0,0 +1,88 @@
+use macroquad::prelude::*;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+// rows between flushes, about once a second at 60fps
+const FLUSH_EVERY: u32 = 60;
+
+pub struct TelemetryRow {
+    pub time: f64,
+    pub plane_position: Vec3,
+    pub speed: f32,
+    pub throttle: bool,
+    pub slew: bool,
+    pub camera_position: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+pub struct FlightRecorder {
+    pub path: String,
+    sender: Sender<TelemetryRow>,
+    writer_thread: JoinHandle<()>,
+}
+
+impl FlightRecorder {
+    // file writing happens on its own thread so a slow disk can't stall the frame
+    pub fn start(path: String) -> std::io::Result<Self> {
+        let file = File::create(&path)?;
+        let (sender, receiver) = channel::<TelemetryRow>();
+
+        let writer_thread = thread::spawn(move || {
+            // a failed write ends the thread, which drops the receiver so record() sees it
+            if let Err(err) = write_rows(file, receiver) {
+                println!("Flight recorder write failed: {}", err);
+            }
+        });
+
+        Ok(FlightRecorder { path, sender, writer_thread })
+    }
+
+    // false once the writer thread has given up
+    pub fn record(&self, row: TelemetryRow) -> bool {
+        self.sender.send(row).is_ok()
+    }
+
+    // closes the channel, the writer keeps draining queued rows in the background
+    // and the returned handle has to be joined before the process exits
+    pub fn stop(self) -> JoinHandle<()> {
+        drop(self.sender);
+        self.writer_thread
+    }
+}
+
+fn write_rows(file: File, receiver: Receiver<TelemetryRow>) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(file);
+    let mut rows = 0;
+    writeln!(
+        writer,
+        "time,plane_x,plane_y,plane_z,speed,throttle,slew,camera_x,camera_y,camera_z,yaw,pitch"
+    )?;
+
+    // ends once the recorder (and its sender) is dropped
+    for row in receiver {
+        writeln!(
+            writer,
+            "{:.4},{:.4},{:.4},{:.4},{:.4},{},{},{:.4},{:.4},{:.4},{:.5},{:.5}",
+            row.time,
+            row.plane_position.x,
+            row.plane_position.y,
+            row.plane_position.z,
+            row.speed,
+            row.throttle,
+            row.slew,
+            row.camera_position.x,
+            row.camera_position.y,
+            row.camera_position.z,
+            row.yaw,
+            row.pitch,
+        )?;
+        rows += 1;
+        if rows % FLUSH_EVERY == 0 {
+            writer.flush()?;
+        }
+    }
+    writer.flush()
+}
@@ -1,5 +1,6 @@
 use macroquad::prelude::*;
-use crate::consts::{MOVE_SPEED, LOOK_SPEED};
+use crate::camera_mode::CameraMode;
+use crate::consts::{MOVE_SPEED, LOOK_SPEED, REFERENCE_HZ};
 
 pub fn handle_input(
     draw_objects: &mut bool,
@@ -15,21 +16,14 @@ pub fn handle_input(
     switch: &mut bool,
     throttle: &mut bool,
     bounds: f32,
-    delta: f32,
+    frame_scale: f32,
     world_up: Vec3,
+    camera_mode: CameraMode,
 ) -> Vec2 { // Return Vec2
 
     // probably pass this all in as a giant game state object
 
     /* #region keyboard input handling */
-    if is_key_pressed(KeyCode::Escape) {
-        std::process::exit(0);
-    }
-    if is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl) {
-        if is_key_pressed(KeyCode::C) {
-            std::process::exit(0);
-        }
-    }
     if is_key_pressed(KeyCode::P) {
         *draw_objects = !*draw_objects;
     }
@@ -42,17 +36,20 @@ pub fn handle_input(
         set_cursor_grab(*grabbed);
         show_mouse(!*grabbed);
     }
-    if is_key_down(KeyCode::W) {
-        *position += *front * MOVE_SPEED;
-    }
-    if is_key_down(KeyCode::A) {
-        *position -= *right * MOVE_SPEED;
-    }
-    if is_key_down(KeyCode::S) {
-        *position -= *front * MOVE_SPEED;
-    }
-    if is_key_down(KeyCode::D) {
-        *position += *right * MOVE_SPEED;
+    // external cameras follow the airplane, so WASD only moves the free camera
+    if camera_mode == CameraMode::Free {
+        if is_key_down(KeyCode::W) {
+            *position += *front * MOVE_SPEED * frame_scale;
+        }
+        if is_key_down(KeyCode::A) {
+            *position -= *right * MOVE_SPEED * frame_scale;
+        }
+        if is_key_down(KeyCode::S) {
+            *position -= *front * MOVE_SPEED * frame_scale;
+        }
+        if is_key_down(KeyCode::D) {
+            *position += *right * MOVE_SPEED * frame_scale;
+        }
     }
 
     let (mouse_x, mouse_y) = mouse_position();
@@ -62,8 +59,12 @@ pub fn handle_input(
     /* #endregion */
 
     /* #region mouse input handling */
-    *yaw += mouse_delta.x * delta * LOOK_SPEED;
-    *pitch += mouse_delta.y * delta * -LOOK_SPEED;
+    // chase ignores mouse look, so leave the free camera pointing where it was
+    if camera_mode != CameraMode::Chase {
+        // the mouse delta is already a whole frame's movement, so it isn't scaled by frame time
+        *yaw += mouse_delta.x * LOOK_SPEED / REFERENCE_HZ;
+        *pitch += mouse_delta.y * -LOOK_SPEED / REFERENCE_HZ;
+    }
 
     *pitch = if *pitch > 1.5 { 1.5 } else { *pitch };
     *pitch = if *pitch < -1.5 { -1.5 } else { *pitch };
@@ -78,7 +79,7 @@ pub fn handle_input(
     *right = front.cross(world_up).normalize();
     *up = right.cross(*front).normalize();
 
-    *x += if *switch { 0.04 } else { -0.04 };
+    *x += (if *switch { 0.04 } else { -0.04 }) * frame_scale;
     if *x >= bounds || *x <= -bounds {
         *switch = !*switch;
     }
@@ -1,7 +1,9 @@
+mod camera_mode;
 mod consts;
 mod logo;
 mod draw_objects;
 mod draw_models;
+mod flight_recorder;
 mod grid;
 mod input_handling;
 mod load_assets;
@@ -9,7 +11,9 @@ mod sim_state;
 
 
 
+use camera_mode::{camera_for_mode, CameraMode};
 use draw_models::draw_models;
+use flight_recorder::{FlightRecorder, TelemetryRow};
 use input_handling::handle_input;
 use load_assets::{Assets, BoundingBox, calculate_aabb, check_collision};
 use macroquad::prelude::*;
@@ -75,11 +79,76 @@ async fn main() {
     let mut grabbed = true;
     set_cursor_grab(grabbed);
     show_mouse(false);
+
+    // closing the window comes through is_quit_requested so recordings can finish
+    prevent_quit();
+
+    let mut fps_cap_index = 0;
+    let mut slew = false;
+
+    let mut camera_mode = CameraMode::Free;
+    let mut chase_direction = vec3(1.0, 0.0, 0.0);
+    let mut orbit_distance = consts::ORBIT_DISTANCE;
+
+    let mut flight_recorder: Option<FlightRecorder> = None;
+    // stopped recorders still flushing to disk, joined on quit
+    let mut recorder_writers: Vec<std::thread::JoinHandle<()>> = Vec::new();
     /* #endregion */
 
 
+    let mut last_frame_start = get_time();
     loop {
+        // pace whole loop periods, so the swap in next_frame counts toward the cap too
+        let now = get_time();
+        last_frame_start = match consts::FPS_CAPS[fps_cap_index] {
+            Some(cap) if now < last_frame_start + 1.0 / cap as f64 => {
+                let deadline = last_frame_start + 1.0 / cap as f64;
+                std::thread::sleep(std::time::Duration::from_secs_f64(deadline - now));
+                deadline
+            }
+            _ => now,
+        };
         let delta = get_frame_time();
+        // how many REFERENCE_HZ frames this frame stands for
+        let frame_scale = delta.min(consts::MAX_FRAME_TIME) * consts::REFERENCE_HZ;
+
+        let ctrl_down = is_key_down(KeyCode::LeftControl) || is_key_down(KeyCode::RightControl);
+        if is_quit_requested()
+            || is_key_pressed(KeyCode::Escape)
+            || (ctrl_down && is_key_pressed(KeyCode::C))
+        {
+            // process::exit skips destructors, so finish the recordings first
+            if let Some(recorder) = flight_recorder.take() {
+                println!("Flight recorder stopped: {}", recorder.path);
+                recorder_writers.push(recorder.stop());
+            }
+            for writer in recorder_writers.drain(..) {
+                let _ = writer.join();
+            }
+            std::process::exit(0);
+        }
+
+        if is_key_pressed(KeyCode::R) {
+            // back to the starting conditions, assets stay loaded
+            plane_position = sim_state.plane_position;
+            speed = sim_state.speed;
+            throttle = sim_state.throttle;
+            slew = false;
+            position = sim_state.position;
+            yaw = sim_state.yaw;
+            pitch = sim_state.pitch;
+            chase_direction = vec3(1.0, 0.0, 0.0);
+            orbit_distance = consts::ORBIT_DISTANCE;
+        }
+
+        if is_key_pressed(KeyCode::V) {
+            // snap the free camera back to just behind the airplane, looking at it
+            camera_mode = CameraMode::Free;
+            position = plane_position + consts::OBSERVER_SNAP_OFFSET;
+            let to_plane = plane_position - position;
+            yaw = to_plane.z.atan2(to_plane.x);
+            pitch = (to_plane.y / to_plane.length()).asin();
+        }
 
         let mouse_position = handle_input(
             &mut draw_objects,
@@ -95,22 +164,39 @@ async fn main() {
             &mut switch,
             &mut throttle,
             bounds,
-            delta,
+            frame_scale,
             world_up,
+            camera_mode,
         );
 
 
         clear_background(consts::FSBLUE);
 
+        if is_key_pressed(KeyCode::C) {
+            camera_mode = camera_mode.next();
+        }
+        if camera_mode == CameraMode::Orbit {
+            let (_, wheel) = mouse_wheel();
+            if wheel > 0.0 {
+                orbit_distance = (orbit_distance * consts::ORBIT_ZOOM_STEP).max(consts::ORBIT_MIN_DISTANCE);
+            } else if wheel < 0.0 {
+                orbit_distance = (orbit_distance / consts::ORBIT_ZOOM_STEP).min(consts::ORBIT_MAX_DISTANCE);
+            }
+        }
+
         // Going 3d!
-        set_camera(&Camera3D {
-            position: position,
-            up: up,
-            target: position + front,
-            ..Default::default()
-        });
+        let camera = camera_for_mode(
+            camera_mode,
+            position,
+            front,
+            up,
+            plane_position,
+            chase_direction,
+            orbit_distance,
+        );
+        set_camera(&camera);
 
-        grid::draw_grid_based_on_position(position[1]);
+        grid::draw_grid_based_on_position(camera.position[1]);
 
 
         // Conditionally draw the objects based on the value of draw_objects
@@ -125,45 +211,138 @@ async fn main() {
             println!("Collision detected!");
         }
 
-        // region airplane throttle
-        if throttle {
-            speed += 0.01;
-        };
-        if !throttle {
-            if speed > 0.0 {
-                speed -= 0.01;
-            }
+        if is_key_pressed(KeyCode::F) {
+            fps_cap_index = (fps_cap_index + 1) % consts::FPS_CAPS.len();
         }
-        /* #endregion */
 
-        /* #region handle airplane speed and direction */
-        if speed > 0.0 {
-            plane_position[0] += speed;
+        let last_plane_position = plane_position;
+
+        if is_key_pressed(KeyCode::Y) {
+            slew = !slew;
+            // either way the airplane starts from rest
+            speed = 0.0;
+            throttle = false;
         }
 
-        if is_key_down(KeyCode::Right) {
-            plane_position[2] += speed * 0.12;
+        if slew {
+            /* #region slew the airplane */
+            let slew_step = consts::SLEW_SPEED * frame_scale;
+            if is_key_down(KeyCode::Up) {
+                plane_position[0] += slew_step;
+            }
+            if is_key_down(KeyCode::Down) {
+                plane_position[0] -= slew_step;
+            }
+            if is_key_down(KeyCode::Right) {
+                plane_position[2] += slew_step;
+            }
+            if is_key_down(KeyCode::Left) {
+                plane_position[2] -= slew_step;
+            }
+            if is_key_down(KeyCode::PageUp) {
+                plane_position[1] += slew_step;
+            }
+            if is_key_down(KeyCode::PageDown) {
+                plane_position[1] = (plane_position[1] - slew_step).max(0.0);
+            }
+            /* #endregion */
+        } else {
+            // region airplane throttle
+            if throttle {
+                speed += 0.01 * frame_scale;
+            };
+            if !throttle {
+                if speed > 0.0 {
+                    speed = (speed - 0.01 * frame_scale).max(0.0);
+                }
+            }
+            /* #endregion */
+
+            /* #region handle airplane speed and direction */
+            if speed > 0.0 {
+                plane_position[0] += speed * frame_scale;
+            }
+
+            if is_key_down(KeyCode::Right) {
+                plane_position[2] += speed * 0.12 * frame_scale;
+            }
+            if is_key_down(KeyCode::Left) {
+                plane_position[2] -= speed * 0.12 * frame_scale;
+            }
+            if speed > 0.5 {
+                plane_position[1] += 0.5 * frame_scale;
+            }
+            if speed < 0.5 {
+                if plane_position[1] > 0.0 {
+                    plane_position[1] -= 1.0 * frame_scale;
+                }
+            }
+            /* #endregion */
         }
-        if is_key_down(KeyCode::Left) {
-            plane_position[2] -= speed * 0.12;
+
+        // chase along the ground track so a falling airplane doesn't put the camera overhead,
+        // slewing backward or sideways isn't flying so it leaves the chase direction alone
+        let ground_velocity = (plane_position - last_plane_position) * vec3(1.0, 0.0, 1.0);
+        if !slew && ground_velocity.length() > 0.001 {
+            chase_direction = ground_velocity.normalize();
         }
-        if speed > 0.5 {
-            plane_position[1] += 0.5;
+
+        if is_key_pressed(KeyCode::L) {
+            flight_recorder = match flight_recorder.take() {
+                Some(recorder) => {
+                    println!("Flight recorder stopped: {}", recorder.path);
+                    recorder_writers.retain(|writer| !writer.is_finished());
+                    recorder_writers.push(recorder.stop());
+                    None
+                }
+                None => {
+                    let started = std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map_or(0, |elapsed| elapsed.as_secs());
+                    match FlightRecorder::start(format!("flight_{}.csv", started)) {
+                        Ok(recorder) => {
+                            println!("Flight recorder started: {}", recorder.path);
+                            Some(recorder)
+                        }
+                        Err(err) => {
+                            println!("Could not start flight recorder: {}", err);
+                            None
+                        }
+                    }
+                }
+            };
         }
-        if speed < 0.5 {
-            if plane_position[1] > 0.0 {
-                plane_position[1] -= 1.0;
+        if let Some(recorder) = &flight_recorder {
+            let recorded = recorder.record(TelemetryRow {
+                time: get_time(),
+                plane_position,
+                speed,
+                throttle,
+                slew,
+                camera_position: camera.position,
+                yaw,
+                pitch,
+            });
+            if !recorded {
+                // the writer thread already reported the error and exited
+                println!("Flight recorder stopped: {}", recorder.path);
+                flight_recorder = None;
             }
         }
-        /* #endregion */
 
         // Back to screen space, render some text
 
         set_default_camera();
 
         /* #region draw text */
-        draw_text("First Person Camera", 10.0, 20.0, 30.0, WHITE);
+        draw_text(format!("{} <C>", camera_mode.label()).as_str(), 10.0, 20.0, 30.0, WHITE);
 
+        if slew {
+            draw_text("SLEW <Y>", 10.0, 48.0 + 90.0, 30.0, YELLOW);
+        }
+        if flight_recorder.is_some() {
+            draw_text("REC <L>", 10.0, 48.0 + 114.0, 30.0, RED);
+        }
     
         draw_text(
             format!("X: {} Y: {}", mouse_position.x, mouse_position.y).as_str(),
@@ -181,8 +360,22 @@ async fn main() {
             WHITE,
         );
 
+        let fps_cap = consts::FPS_CAPS[fps_cap_index];
+        draw_text(
+            format!(
+                "FPS: {} cap: {} <F>",
+                get_fps(),
+                fps_cap.map_or(String::from("off"), |cap| cap.to_string())
+            )
+            .as_str(),
+            10.0,
+            48.0 + 66.0,
+            30.0,
+            WHITE,
+        );
+
         // Calculate the altitude via x-coordinate for the top right corner and draw the text
-        let altitude = position[1].round();
+        let altitude = camera.position[1].round();
         let text = if altitude > 18000.0 {
             // do crazy flight level stuff to be fancy
             format!("FL{:03}", (altitude / 100.0).round() as i32)
@@ -195,7 +388,7 @@ async fn main() {
         /* #endregion */
 
         // Increment the rotation angle
-        rotation_angle += 1.0;
+        rotation_angle += 1.0 * frame_scale;
         macroquad_profiler::profiler(Default::default());
         next_frame().await; 
     }